@@ -0,0 +1,183 @@
+// Inbound idempotency: upstream providers frequently re-send the same webhook,
+// so we track recently-seen keys and short-circuit retransmits instead of
+// re-broadcasting them to every endpoint.
+use crate::routing;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+const DEFAULT_TTL_SECS: u64 = 300;
+const DEFAULT_HEADER: &str = "x-idempotency-key";
+const DEFAULT_JSON_PATH: &str = "$.id";
+
+// Where to read the idempotency key from for a given source, e.g.
+// `IDEMPOTENCY_KEY_SOURCE_FINCRA=json:$.data.id` or `...=header:X-Event-Id`.
+enum KeySource {
+    Header(String),
+    JsonPath(String),
+}
+
+fn key_source_for(source: &str) -> KeySource {
+    let env_key = format!("IDEMPOTENCY_KEY_SOURCE_{}", source.to_uppercase());
+    match std::env::var(env_key).ok() {
+        Some(spec) => match spec.split_once(':') {
+            Some(("header", header)) => KeySource::Header(header.to_string()),
+            Some(("json", path)) => KeySource::JsonPath(path.to_string()),
+            _ => KeySource::Header(DEFAULT_HEADER.to_string()),
+        },
+        None => KeySource::Header(DEFAULT_HEADER.to_string()),
+    }
+}
+
+// TTL for a source's seen-keys, configurable per source with a global fallback
+fn ttl_for(source: &str) -> u64 {
+    let per_source = format!("IDEMPOTENCY_TTL_SECONDS_{}", source.to_uppercase());
+    std::env::var(per_source)
+        .ok()
+        .or_else(|| std::env::var("IDEMPOTENCY_TTL_SECONDS").ok())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TTL_SECS)
+}
+
+// Compute the idempotency key for an inbound event, falling back from the
+// configured header to a `$.id`-style JSON path so requests still dedup
+// without any per-source configuration.
+pub fn compute_key(
+    source: &str,
+    headers: &HashMap<String, String>,
+    payload: &serde_json::Value,
+) -> Option<String> {
+    let key = match key_source_for(source) {
+        KeySource::Header(header_name) => headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&header_name))
+            .map(|(_, value)| value.clone()),
+        KeySource::JsonPath(path) => routing::resolve_path(payload, &path)
+            .and_then(|v| v.as_str().map(|s| s.to_string()).or_else(|| Some(v.to_string()))),
+    };
+
+    key.or_else(|| {
+        routing::resolve_path(payload, DEFAULT_JSON_PATH)
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+    })
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// Bounded, TTL'd store of recently-seen idempotency keys. Oldest entries are
+// evicted once capacity is exceeded, and expired entries are purged lazily.
+pub struct SeenKeys {
+    capacity: usize,
+    entries: RwLock<(HashMap<String, u64>, VecDeque<String>)>,
+}
+
+impl SeenKeys {
+    pub fn new() -> Self {
+        SeenKeys::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        SeenKeys {
+            capacity,
+            entries: RwLock::new((HashMap::new(), VecDeque::new())),
+        }
+    }
+
+    // Returns true if `source`+`key` was already seen within its TTL window;
+    // otherwise records it and returns false.
+    pub fn check_and_record(&self, source: &str, key: &str) -> bool {
+        let full_key = format!("{}:{}", source, key);
+        let ttl_secs = ttl_for(source);
+        let current = now();
+
+        let mut guard = self.entries.write().unwrap();
+        let (index, order) = &mut *guard;
+
+        if let Some(&expires_at) = index.get(&full_key) {
+            if expires_at > current {
+                return true;
+            }
+            // Expired: drop the stale `order` entry too, so eviction below can't
+            // later remove the live re-insertion by mistaking it for this one.
+            index.remove(&full_key);
+            if let Some(pos) = order.iter().position(|k| k == &full_key) {
+                order.remove(pos);
+            }
+        }
+
+        index.insert(full_key.clone(), current + ttl_secs);
+        order.push_back(full_key);
+
+        while order.len() > self.capacity {
+            if let Some(evicted) = order.pop_front() {
+                index.remove(&evicted);
+            }
+        }
+
+        false
+    }
+}
+
+impl Default for SeenKeys {
+    fn default() -> Self {
+        SeenKeys::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_and_record_suppresses_an_immediate_repeat() {
+        let seen = SeenKeys::new();
+        assert!(!seen.check_and_record("dedup_test_repeat", "key-1"));
+        assert!(seen.check_and_record("dedup_test_repeat", "key-1"));
+    }
+
+    #[test]
+    fn check_and_record_treats_different_keys_independently() {
+        let seen = SeenKeys::new();
+        assert!(!seen.check_and_record("dedup_test_distinct", "key-1"));
+        assert!(!seen.check_and_record("dedup_test_distinct", "key-2"));
+    }
+
+    #[test]
+    fn check_and_record_still_suppresses_a_live_key_after_capacity_eviction() {
+        // Regression test: re-seeing an expired key used to leave a stale copy
+        // in the eviction order, so evicting that stale copy deleted the
+        // freshly re-inserted live entry, letting a duplicate through.
+        let source = "dedup_test_reexpire";
+        std::env::set_var(
+            format!("IDEMPOTENCY_TTL_SECONDS_{}", source.to_uppercase()),
+            "1",
+        );
+
+        // Capacity 1, so a stale duplicate in the eviction order would push the
+        // live entry out on re-insertion.
+        let seen = SeenKeys::with_capacity(1);
+        assert!(!seen.check_and_record(source, "key-1"));
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        // TTL has lapsed: treated as a fresh key and re-recorded as live.
+        assert!(!seen.check_and_record(source, "key-1"));
+        // Immediately re-seen within the new TTL window: must be suppressed.
+        assert!(seen.check_and_record(source, "key-1"));
+
+        std::env::remove_var(format!("IDEMPOTENCY_TTL_SECONDS_{}", source.to_uppercase()));
+    }
+
+    #[test]
+    fn check_and_record_evicts_oldest_entries_past_capacity() {
+        let seen = SeenKeys::with_capacity(1);
+        assert!(!seen.check_and_record("dedup_test_capacity", "key-1"));
+        assert!(!seen.check_and_record("dedup_test_capacity", "key-2"));
+        // "key-1" was evicted to make room for "key-2", so it's no longer suppressed.
+        assert!(!seen.check_and_record("dedup_test_capacity", "key-1"));
+    }
+}
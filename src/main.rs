@@ -6,26 +6,83 @@ use futures::future;
 use reqwest; // Using reqwest instead of awc for better thread safety
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
-use std::sync::RwLock;
+use std::sync::Arc;
+
+mod dedup;
+mod metrics;
+mod queue;
+mod routing;
+mod signing;
+mod storage;
+
+use dedup::SeenKeys;
+use queue::DeliveryQueue;
+use routing::{EventFilterRule, TransformRule};
+use storage::{DeliveryRecord, Storage};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct WebhookEvent {
+pub struct WebhookEvent {
     #[serde(flatten)]
     payload: serde_json::Value,
     #[serde(default)]
     headers: HashMap<String, String>,
+    // The idempotency key computed for this event, if any, carried along so
+    // every forward attempt's delivery record can be traced back to it.
+    #[serde(default)]
+    idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-struct WebhookEndpoint {
+pub struct WebhookEndpoint {
     #[serde(default)]
     id: String,
     url: String,
     name: String,
     #[serde(default)]
     is_active: bool,
+    // Shared secret used to sign outbound deliveries; never logged.
+    #[serde(default)]
+    signing_secret: Option<String>,
+    // Only forward events whose payload matches every rule (AND semantics).
+    // `None`/empty means forward everything, preserving today's behavior.
+    #[serde(default)]
+    event_filter: Option<Vec<EventFilterRule>>,
+    // Explicit set of request headers to forward. `None` falls back to the
+    // previous forward-everything-except-Host behavior for existing configs.
+    #[serde(default)]
+    header_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    transform: Option<TransformRule>,
+}
+
+// What the API actually returns for an endpoint. `signing_secret` never appears
+// here — these routes have no auth in front of them, so echoing the secret back
+// would let anyone who can call the API recover it in plaintext.
+#[derive(Debug, Serialize)]
+struct WebhookEndpointResponse {
+    id: String,
+    url: String,
+    name: String,
+    is_active: bool,
+    has_signing_secret: bool,
+    event_filter: Option<Vec<EventFilterRule>>,
+    header_allowlist: Option<Vec<String>>,
+    transform: Option<TransformRule>,
+}
+
+impl From<&WebhookEndpoint> for WebhookEndpointResponse {
+    fn from(endpoint: &WebhookEndpoint) -> Self {
+        WebhookEndpointResponse {
+            id: endpoint.id.clone(),
+            url: endpoint.url.clone(),
+            name: endpoint.name.clone(),
+            is_active: endpoint.is_active,
+            has_signing_secret: endpoint.signing_secret.is_some(),
+            event_filter: endpoint.event_filter.clone(),
+            header_allowlist: endpoint.header_allowlist.clone(),
+            transform: endpoint.transform.clone(),
+        }
+    }
 }
 
 // Add this new struct for the registration request
@@ -35,10 +92,20 @@ struct CreateWebhookRequest {
     name: String,
     #[serde(default)]
     is_active: bool,
+    #[serde(default)]
+    signing_secret: Option<String>,
+    #[serde(default)]
+    event_filter: Option<Vec<EventFilterRule>>,
+    #[serde(default)]
+    header_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    transform: Option<TransformRule>,
 }
 
 struct AppState {
-    endpoints: RwLock<Vec<WebhookEndpoint>>,
+    storage: Box<dyn Storage>,
+    delivery_queue: Arc<DeliveryQueue>,
+    seen_keys: SeenKeys,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -46,6 +113,20 @@ struct EndpointUpdate {
     is_active: bool,
 }
 
+// Full replacement of an endpoint's routing config (filter/allowlist/transform).
+// A dedicated route rather than folding these into `EndpointUpdate` so that
+// callers toggling `is_active` via `/status` can't silently wipe routing rules
+// they never intended to touch.
+#[derive(Debug, Serialize, Deserialize)]
+struct EndpointRoutingUpdate {
+    #[serde(default)]
+    event_filter: Option<Vec<EventFilterRule>>,
+    #[serde(default)]
+    header_allowlist: Option<Vec<String>>,
+    #[serde(default)]
+    transform: Option<TransformRule>,
+}
+
 // Endpoint to register new webhook endpoint
 async fn register_endpoint(
     endpoint_req: web::Json<CreateWebhookRequest>,
@@ -71,23 +152,51 @@ async fn register_endpoint(
         url: endpoint_req.url.clone(),
         name: endpoint_req.name.clone(),
         is_active: endpoint_req.is_active,
+        signing_secret: endpoint_req.signing_secret.clone(),
+        event_filter: endpoint_req.event_filter.clone(),
+        header_allowlist: endpoint_req.header_allowlist.clone(),
+        transform: endpoint_req.transform.clone(),
     };
 
-    let mut endpoints = data.endpoints.write().unwrap();
-    endpoints.push(new_endpoint.clone());
-
-    // Save updated endpoints to persistent storage
-    if let Err(e) = save_endpoints(&endpoints) {
-        println!("Error saving endpoints: {}", e);
+    if let Err(e) = data.storage.upsert_endpoint(new_endpoint).await {
+        println!("Error saving endpoint: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to save endpoint"
+        }));
     }
 
-    HttpResponse::Ok().json(endpoints.clone())
+    match data.storage.list_endpoints().await {
+        Ok(endpoints) => {
+            let response: Vec<WebhookEndpointResponse> = endpoints.iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            println!("Error listing endpoints: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
 }
 
 // Endpoint to list all registered webhooks
 async fn list_endpoints(data: web::Data<AppState>) -> HttpResponse {
-    let endpoints = data.endpoints.read().unwrap();
-    HttpResponse::Ok().json(endpoints.clone())
+    match data.storage.list_endpoints().await {
+        Ok(endpoints) => {
+            metrics::set_active_endpoints(endpoints.iter().filter(|e| e.is_active).count());
+            let response: Vec<WebhookEndpointResponse> = endpoints.iter().map(Into::into).collect();
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            println!("Error listing endpoints: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+// Render current metrics in Prometheus text exposition format
+async fn metrics_handler() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics::render())
 }
 
 // Update endpoint status (active/inactive)
@@ -97,22 +206,74 @@ async fn update_endpoint(
     data: web::Data<AppState>,
 ) -> HttpResponse {
     let id = path.into_inner();
-    let mut endpoints = data.endpoints.write().unwrap();
+    let endpoints = match data.storage.list_endpoints().await {
+        Ok(endpoints) => endpoints,
+        Err(e) => {
+            println!("Error listing endpoints: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let Some(mut endpoint) = endpoints.into_iter().find(|e| e.id == id) else {
+        return HttpResponse::NotFound().finish();
+    };
+    endpoint.is_active = update.is_active;
 
-    if let Some(endpoint) = endpoints.iter_mut().find(|e| e.id == id) {
-        endpoint.is_active = update.is_active;
+    if let Err(e) = data.storage.upsert_endpoint(endpoint.clone()).await {
+        println!("Error saving endpoint: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to save endpoint"
+        }));
+    }
 
-        // Clone endpoint for response
-        let endpoint_clone = endpoint.clone();
+    HttpResponse::Ok().json(WebhookEndpointResponse::from(&endpoint))
+}
 
-        // Save the updated endpoints
-        if let Err(e) = save_endpoints(&endpoints) {
-            println!("Error saving endpoints: {}", e);
+// Update an endpoint's routing config (event filter, header allowlist, transform).
+// Kept separate from `update_endpoint` so toggling `is_active` never touches these.
+async fn update_endpoint_routing(
+    path: web::Path<String>,
+    update: web::Json<EndpointRoutingUpdate>,
+    data: web::Data<AppState>,
+) -> HttpResponse {
+    let id = path.into_inner();
+    let endpoints = match data.storage.list_endpoints().await {
+        Ok(endpoints) => endpoints,
+        Err(e) => {
+            println!("Error listing endpoints: {}", e);
+            return HttpResponse::InternalServerError().finish();
         }
+    };
 
-        HttpResponse::Ok().json(endpoint_clone)
-    } else {
-        HttpResponse::NotFound().finish()
+    let Some(mut endpoint) = endpoints.into_iter().find(|e| e.id == id) else {
+        return HttpResponse::NotFound().finish();
+    };
+    endpoint.event_filter = update.event_filter.clone();
+    endpoint.header_allowlist = update.header_allowlist.clone();
+    endpoint.transform = update.transform.clone();
+
+    if let Err(e) = data.storage.upsert_endpoint(endpoint.clone()).await {
+        println!("Error saving endpoint: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to save endpoint"
+        }));
+    }
+
+    HttpResponse::Ok().json(WebhookEndpointResponse::from(&endpoint))
+}
+
+// Endpoint to list recorded delivery attempts for a given endpoint
+async fn list_deliveries(
+    endpoint_id: web::Path<String>,
+    data: web::Data<AppState>,
+) -> HttpResponse {
+    let id = endpoint_id.into_inner();
+    match data.storage.list_deliveries(&id).await {
+        Ok(deliveries) => HttpResponse::Ok().json(deliveries),
+        Err(e) => {
+            println!("Error listing deliveries: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
     }
 }
 
@@ -122,29 +283,47 @@ async fn delete_endpoint(
     data: web::Data<AppState>,
 ) -> HttpResponse {
     let id = endpoint_id.into_inner();
-    let mut endpoints = data.endpoints.write().unwrap();
-    if let Some(pos) = endpoints.iter().position(|e| e.id == id) {
-        endpoints.remove(pos);
-
-        // Save the updated endpoints
-        if let Err(e) = save_endpoints(&endpoints) {
-            println!("Error saving endpoints: {}", e);
+    match data.storage.delete_endpoint(&id).await {
+        Ok(true) => match data.storage.list_endpoints().await {
+            Ok(endpoints) => {
+                let response: Vec<WebhookEndpointResponse> =
+                    endpoints.iter().map(Into::into).collect();
+                HttpResponse::Ok().json(response)
+            }
+            Err(e) => {
+                println!("Error listing endpoints: {}", e);
+                HttpResponse::InternalServerError().finish()
+            }
+        },
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(e) => {
+            println!("Error deleting endpoint: {}", e);
+            HttpResponse::InternalServerError().finish()
         }
-
-        HttpResponse::Ok().json(endpoints.clone())
-    } else {
-        HttpResponse::NotFound().finish()
     }
 }
 
 // Forward webhook to specific endpoint
-async fn forward_webhook(
+pub async fn forward_webhook(
     client: &reqwest::Client,
     endpoint: &WebhookEndpoint,
     payload: &WebhookEvent,
-) -> Result<(), String> {
+) -> Result<u16, String> {
+    // Apply the endpoint's transform (field remapping / envelope) before sending
+    let transformed_payload = match &endpoint.transform {
+        Some(transform) => routing::apply_transform(transform, &payload.payload),
+        None => payload.payload.clone(),
+    };
+
+    // Serialize the body once so we can sign the exact bytes we send
+    let body_bytes = serde_json::to_vec(&transformed_payload)
+        .map_err(|e| format!("Failed to serialize payload: {}", e))?;
+
     // Create a custom client that doesn't add a Host header automatically
-    let mut request_builder = client.post(&endpoint.url).json(&payload.payload);
+    let mut request_builder = client
+        .post(&endpoint.url)
+        .header("Content-Type", "application/json")
+        .body(body_bytes.clone());
 
     // Get the URL hostname to set as Host header
     let url = url::Url::parse(&endpoint.url).map_err(|e| format!("Failed to parse URL: {}", e))?;
@@ -163,14 +342,31 @@ async fn forward_webhook(
     // Set the proper Host header for the target URL
     request_builder = request_builder.header("Host", host_header);
 
-    // Forward selected original headers, but skip the Host header
+    // Forward original headers per the endpoint's allowlist. Endpoints without an
+    // allowlist configured keep the previous forward-everything-except-Host behavior.
     for (header_name, header_value) in &payload.headers {
-        // Skip the original Host header to avoid misdirected request errors
-        if header_name.to_lowercase() != "host" {
+        if header_name.to_lowercase() == "host" {
+            continue;
+        }
+        let allowed = match &endpoint.header_allowlist {
+            Some(allowlist) => allowlist
+                .iter()
+                .any(|allowed_name| allowed_name.eq_ignore_ascii_case(header_name)),
+            None => true,
+        };
+        if allowed {
             request_builder = request_builder.header(header_name, header_value);
         }
     }
 
+    // Sign the outbound delivery so the receiver can authenticate Mavapay as the sender
+    if let Some(secret) = &endpoint.signing_secret {
+        let signature = signing::sign_hex(secret, &body_bytes);
+        request_builder = request_builder
+            .header("X-Webhook-Signature", format!("sha256={}", signature))
+            .header("X-Webhook-Timestamp", signing::unix_timestamp().to_string());
+    }
+
     let response = request_builder
         .send()
         .await
@@ -182,7 +378,7 @@ async fn forward_webhook(
             "Successfully forwarded to {}: status {}",
             endpoint.name, status
         );
-        Ok(())
+        Ok(status.as_u16())
     } else {
         let error_body = response
             .text()
@@ -195,11 +391,42 @@ async fn forward_webhook(
     }
 }
 
+// Forward a webhook and record the outcome (status, latency, error) in storage
+// so operators can see delivery history beyond what the logs retain.
+pub(crate) async fn forward_and_record(
+    storage: &dyn Storage,
+    client: &reqwest::Client,
+    endpoint: &WebhookEndpoint,
+    payload: &WebhookEvent,
+) -> Result<u16, String> {
+    metrics::record_delivery_attempt(&endpoint.name);
+    let started_at = std::time::Instant::now();
+    let result = forward_webhook(client, endpoint, payload).await;
+    let latency_ms = started_at.elapsed().as_millis();
+    metrics::record_forward_latency(&endpoint.name, latency_ms);
+    metrics::record_delivery_result(&endpoint.name, result.is_ok());
+
+    let record = DeliveryRecord {
+        endpoint_id: endpoint.id.clone(),
+        status_code: result.as_ref().ok().copied(),
+        latency_ms,
+        timestamp: signing::unix_timestamp(),
+        error: result.as_ref().err().cloned(),
+        idempotency_key: payload.idempotency_key.clone(),
+    };
+    if let Err(e) = storage.record_delivery(record).await {
+        println!("Error recording delivery for {}: {}", endpoint.name, e);
+    }
+
+    result
+}
+
 // New function to handle specific webhook paths
 async fn handle_specific_webhook(
     path: web::Path<String>,
-    payload: web::Json<serde_json::Value>,
+    body: web::Bytes,
     req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> HttpResponse {
     let service = path.into_inner();
     let destination_url = match service.as_str() {
@@ -218,13 +445,40 @@ async fn handle_specific_webhook(
         }
     }
 
+    // Verify the inbound signature (if this source has a secret configured) against
+    // the raw request body before we ever parse it as JSON
+    if let Err(e) = signing::verify_inbound(&service, &headers, &body) {
+        return HttpResponse::Unauthorized().json(serde_json::json!({ "error": e }));
+    }
+
+    let payload: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(value) => value,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid JSON body",
+                "details": e.to_string()
+            }))
+        }
+    };
+    metrics::record_received(&service);
+
+    // Short-circuit retransmits of an event we've already processed
+    let idempotency_key = dedup::compute_key(&service, &headers, &payload);
+    if let Some(key) = &idempotency_key {
+        if data.seen_keys.check_and_record(&service, key) {
+            return HttpResponse::Ok().json(serde_json::json!({ "status": "duplicate" }));
+        }
+    }
+
     // Create WebhookEvent with the payload and headers
     let webhook_event = WebhookEvent {
-        payload: payload.into_inner(),
+        payload,
         headers,
+        idempotency_key,
     };
 
-    // Forward the webhook asynchronously
+    // Forward the webhook asynchronously, enqueueing for retry on failure
+    let state = data.clone();
     rt::spawn(async move {
         let client = reqwest::Client::new();
         let endpoint = WebhookEndpoint {
@@ -232,10 +486,17 @@ async fn handle_specific_webhook(
             url: destination_url.to_string(),
             name: format!("Static {} endpoint", service),
             is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
         };
 
-        if let Err(error) = forward_webhook(&client, &endpoint, &webhook_event).await {
+        if let Err(error) =
+            forward_and_record(state.storage.as_ref(), &client, &endpoint, &webhook_event).await
+        {
             println!("Error forwarding to {}: {}", service, error);
+            state.delivery_queue.enqueue(&endpoint.id, &webhook_event);
         }
     });
 
@@ -247,7 +508,7 @@ async fn handle_specific_webhook(
 
 // Webhook receiver endpoint that forwards to active endpoints
 async fn receive_webhook(
-    payload: web::Json<serde_json::Value>,
+    body: web::Bytes,
     req: HttpRequest,
     data: web::Data<AppState>,
 ) -> HttpResponse {
@@ -259,15 +520,56 @@ async fn receive_webhook(
         }
     }
 
+    // Verify the inbound signature (if a secret is configured for this source) against
+    // the raw request body before we ever parse it as JSON
+    if let Err(e) = signing::verify_inbound("webhook", &headers, &body) {
+        return HttpResponse::Unauthorized().json(serde_json::json!({ "error": e }));
+    }
+
+    let payload: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(value) => value,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid JSON body",
+                "details": e.to_string()
+            }))
+        }
+    };
+    metrics::record_received("webhook");
+
+    // Short-circuit retransmits of an event we've already processed
+    let idempotency_key = dedup::compute_key("webhook", &headers, &payload);
+    if let Some(key) = &idempotency_key {
+        if data.seen_keys.check_and_record("webhook", key) {
+            return HttpResponse::Ok().json(serde_json::json!({ "status": "duplicate" }));
+        }
+    }
+
     // Create WebhookEvent with the payload and headers
     let webhook_event = WebhookEvent {
-        payload: payload.into_inner(),
+        payload,
         headers,
+        idempotency_key,
     };
 
-    let endpoints = data.endpoints.read().unwrap();
-    let active_endpoints: Vec<WebhookEndpoint> =
-        endpoints.iter().filter(|e| e.is_active).cloned().collect();
+    let endpoints = match data.storage.list_endpoints().await {
+        Ok(endpoints) => endpoints,
+        Err(e) => {
+            println!("Error listing endpoints: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+    // Only route to endpoints that are active and whose event_filter (if any) matches
+    let active_endpoints: Vec<WebhookEndpoint> = endpoints
+        .into_iter()
+        .filter(|e| e.is_active)
+        .filter(|e| {
+            e.event_filter
+                .as_ref()
+                .map(|rules| routing::matches(rules, &webhook_event.payload))
+                .unwrap_or(true)
+        })
+        .collect();
 
     if active_endpoints.is_empty() {
         return HttpResponse::Ok().json(serde_json::json!({
@@ -278,6 +580,7 @@ async fn receive_webhook(
 
     // Clone the webhook event for async processing
     let webhook_event_clone = webhook_event.clone();
+    let state = data.clone();
 
     // Spawn a new task to process the webhook asynchronously
     rt::spawn(async move {
@@ -292,10 +595,15 @@ async fn receive_webhook(
             .map(|endpoint| {
                 let client = client.clone(); // Clone the client for each future
                 let payload = webhook_event_clone.clone(); // Clone the payload for each future
+                let state = state.clone();
 
                 async move {
-                    if let Err(error) = forward_webhook(&client, &endpoint, &payload).await {
+                    if let Err(error) =
+                        forward_and_record(state.storage.as_ref(), &client, &endpoint, &payload)
+                            .await
+                    {
                         println!("Error forwarding to {}: {}", endpoint.name, error);
+                        state.delivery_queue.enqueue(&endpoint.id, &payload);
                         (endpoint.name, error)
                     } else {
                         (endpoint.name, "Success".to_string())
@@ -322,65 +630,6 @@ async fn receive_webhook(
     }))
 }
 
-// Save endpoints to a JSON file
-fn save_endpoints(endpoints: &[WebhookEndpoint]) -> Result<(), String> {
-    let json = serde_json::to_string_pretty(endpoints)
-        .map_err(|e| format!("Failed to serialize endpoints: {}", e))?;
-
-    fs::write("endpoints.json", json).map_err(|e| format!("Failed to write endpoints file: {}", e))
-}
-
-// Load endpoints from a JSON file
-fn load_endpoints() -> Vec<WebhookEndpoint> {
-    if Path::new("endpoints.json").exists() {
-        match fs::read_to_string("endpoints.json") {
-            Ok(contents) => match serde_json::from_str::<Vec<WebhookEndpoint>>(&contents) {
-                Ok(endpoints) => {
-                    println!("Loaded {} endpoints from file", endpoints.len());
-                    return endpoints;
-                }
-                Err(e) => println!("Error parsing endpoints file: {}", e),
-            },
-            Err(e) => println!("Error reading endpoints file: {}", e),
-        }
-    }
-
-    // Return default endpoints with our staging URLs
-    let default_endpoints = vec![
-        WebhookEndpoint {
-            id: "fincra".to_string(),
-            url: "https://staging.webhook.api.mavapay.co/webhook/fincra".to_string(),
-            name: "Fincra Staging".to_string(),
-            is_active: true,
-        },
-        WebhookEndpoint {
-            id: "splice".to_string(),
-            url: "https://staging.webhook.api.mavapay.co/webhook/splice".to_string(),
-            name: "Splice Staging".to_string(),
-            is_active: true,
-        },
-        WebhookEndpoint {
-            id: "useorange".to_string(),
-            url: "https://staging.webhook.api.mavapay.co/webhook/useorange".to_string(),
-            name: "UseOrange Staging".to_string(),
-            is_active: true,
-        },
-        WebhookEndpoint {
-            id: "galoy".to_string(),
-            url: "https://staging.webhook.api.mavapay.co/webhook/galoy".to_string(),
-            name: "Galoy Staging".to_string(),
-            is_active: true,
-        },
-    ];
-
-    // Save the default endpoints
-    if let Err(e) = save_endpoints(&default_endpoints) {
-        println!("Error saving default endpoints: {}", e);
-    }
-
-    default_endpoints
-}
-
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let port = std::env::var("PORT").unwrap_or_else(|_| "8080".to_string());
@@ -388,13 +637,22 @@ async fn main() -> std::io::Result<()> {
 
     println!("Starting webhook relay server on {}", bind_address);
 
-    // Load endpoints from persistent storage
-    let endpoints = load_endpoints();
+    // Install the global Prometheus recorder used by the `metrics` module
+    metrics::init();
+
+    // Storage backend selected via STORAGE_BACKEND (file, memory, postgres)
+    let storage = storage::from_env().await;
+    let delivery_queue = Arc::new(DeliveryQueue::load());
 
     let app_state = web::Data::new(AppState {
-        endpoints: RwLock::new(endpoints),
+        storage,
+        delivery_queue: delivery_queue.clone(),
+        seen_keys: SeenKeys::new(),
     });
 
+    // Background worker that retries queued deliveries with backoff
+    rt::spawn(queue::run_worker(delivery_queue, app_state.clone()));
+
     HttpServer::new(move || {
         let cors = Cors::permissive(); // For development only
 
@@ -409,7 +667,16 @@ async fn main() -> std::io::Result<()> {
             .route("/endpoints", web::post().to(register_endpoint))
             .route("/endpoints", web::get().to(list_endpoints))
             .route("/endpoints/{id}", web::delete().to(delete_endpoint))
+            .route(
+                "/endpoints/{id}/deliveries",
+                web::get().to(list_deliveries),
+            )
             .route("/endpoints/{id}/status", web::put().to(update_endpoint))
+            .route(
+                "/endpoints/{id}/routing",
+                web::put().to(update_endpoint_routing),
+            )
+            .route("/metrics", web::get().to(metrics_handler))
             .service(actix_files::Files::new("/", "./static").index_file("index.html"))
     })
     .bind(&bind_address)?
@@ -0,0 +1,48 @@
+// Prometheus metrics: a global recorder so detached `rt::spawn` tasks can
+// record outcomes without needing a handle threaded through them, and a
+// `PrometheusHandle` stashed in a `OnceLock` for the `/metrics` route to render.
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use std::sync::OnceLock;
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+// Install the global Prometheus recorder. Call once, before serving traffic.
+pub fn init() {
+    match PrometheusBuilder::new().install_recorder() {
+        Ok(handle) => {
+            let _ = HANDLE.set(handle);
+        }
+        Err(e) => println!("Error installing Prometheus recorder: {}", e),
+    }
+}
+
+pub fn render() -> String {
+    HANDLE.get().map(|h| h.render()).unwrap_or_default()
+}
+
+pub fn record_received(source: &str) {
+    metrics::counter!("webhooks_received_total", "source" => source.to_string()).increment(1);
+}
+
+pub fn record_delivery_attempt(endpoint: &str) {
+    metrics::counter!("webhook_deliveries_attempted_total", "endpoint" => endpoint.to_string())
+        .increment(1);
+}
+
+pub fn record_delivery_result(endpoint: &str, success: bool) {
+    let metric_name = if success {
+        "webhook_deliveries_succeeded_total"
+    } else {
+        "webhook_deliveries_failed_total"
+    };
+    metrics::counter!(metric_name, "endpoint" => endpoint.to_string()).increment(1);
+}
+
+pub fn record_forward_latency(endpoint: &str, latency_ms: u128) {
+    metrics::histogram!("webhook_forward_latency_ms", "endpoint" => endpoint.to_string())
+        .record(latency_ms as f64);
+}
+
+pub fn set_active_endpoints(count: usize) {
+    metrics::gauge!("webhook_active_endpoints").set(count as f64);
+}
@@ -0,0 +1,231 @@
+// Durable delivery queue: retries failed webhook forwards with exponential
+// backoff, surviving process restarts by journaling pending jobs to disk.
+use crate::{forward_and_record, AppState, WebhookEvent};
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const QUEUE_FILE: &str = "queue.json";
+const DEAD_LETTER_FILE: &str = "dead_letters.json";
+const BASE_DELAY_SECS: u64 = 5;
+const MAX_DELAY_SECS: u64 = 3600;
+const MAX_ATTEMPTS: u32 = 10;
+const POLL_INTERVAL_SECS: u64 = 1;
+
+// Jobs only reference the endpoint by id and re-fetch it from `Storage` at send
+// time, so a retry always uses the endpoint's current url/secret/routing config
+// rather than a stale snapshot from when the job was first enqueued.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeliveryJob {
+    pub id: String,
+    pub endpoint_id: String,
+    pub payload: WebhookEvent,
+    pub attempts: u32,
+    pub next_attempt_at: u64,
+}
+
+pub struct DeliveryQueue {
+    jobs: RwLock<Vec<DeliveryJob>>,
+}
+
+impl DeliveryQueue {
+    // Load any jobs left over from a previous run so retries survive restarts
+    pub fn load() -> Self {
+        let jobs = load_jobs();
+        println!("Loaded {} pending delivery job(s) from queue", jobs.len());
+        DeliveryQueue {
+            jobs: RwLock::new(jobs),
+        }
+    }
+
+    pub fn enqueue(&self, endpoint_id: &str, payload: &WebhookEvent) {
+        let job = DeliveryJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            endpoint_id: endpoint_id.to_string(),
+            payload: payload.clone(),
+            attempts: 0,
+            next_attempt_at: now(),
+        };
+
+        let mut jobs = self.jobs.write().unwrap();
+        jobs.push(job);
+        if let Err(e) = save_jobs(&jobs) {
+            println!("Error saving delivery queue: {}", e);
+        }
+    }
+
+    fn reschedule(&self, mut job: DeliveryJob) {
+        job.attempts += 1;
+        if job.attempts >= MAX_ATTEMPTS {
+            println!(
+                "Giving up on job {} to endpoint {} after {} attempts, moving to dead letter file",
+                job.id, job.endpoint_id, job.attempts
+            );
+            if let Err(e) = append_dead_letter(&job) {
+                println!("Error writing dead letter for job {}: {}", job.id, e);
+            }
+            return;
+        }
+
+        job.next_attempt_at = now() + backoff_delay_secs(job.attempts);
+        let mut jobs = self.jobs.write().unwrap();
+        jobs.push(job);
+        if let Err(e) = save_jobs(&jobs) {
+            println!("Error saving delivery queue: {}", e);
+        }
+    }
+
+    // Pull out every job whose retry time has arrived, leaving the rest queued
+    fn take_due(&self) -> Vec<DeliveryJob> {
+        let current = now();
+        let mut jobs = self.jobs.write().unwrap();
+        let (due, pending): (Vec<_>, Vec<_>) = jobs
+            .drain(..)
+            .partition(|job| job.next_attempt_at <= current);
+        *jobs = pending;
+        if !due.is_empty() {
+            if let Err(e) = save_jobs(&jobs) {
+                println!("Error saving delivery queue: {}", e);
+            }
+        }
+        due
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// delay = base * 2^attempts, capped, with +/-20% jitter to avoid thundering herds
+fn backoff_delay_secs(attempts: u32) -> u64 {
+    let raw = BASE_DELAY_SECS.saturating_mul(2u64.saturating_pow(attempts));
+    let capped = raw.min(MAX_DELAY_SECS) as i64;
+    let jitter_range = capped / 5;
+    if jitter_range == 0 {
+        return capped as u64;
+    }
+    let jitter = (uuid::Uuid::new_v4().as_u128() % (jitter_range as u128 * 2 + 1)) as i64
+        - jitter_range;
+    (capped + jitter).max(0) as u64
+}
+
+fn save_jobs(jobs: &[DeliveryJob]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(jobs)
+        .map_err(|e| format!("Failed to serialize delivery queue: {}", e))?;
+    fs::write(QUEUE_FILE, json).map_err(|e| format!("Failed to write queue file: {}", e))
+}
+
+fn load_jobs() -> Vec<DeliveryJob> {
+    if Path::new(QUEUE_FILE).exists() {
+        match fs::read_to_string(QUEUE_FILE) {
+            Ok(contents) => match serde_json::from_str::<Vec<DeliveryJob>>(&contents) {
+                Ok(jobs) => return jobs,
+                Err(e) => println!("Error parsing queue file: {}", e),
+            },
+            Err(e) => println!("Error reading queue file: {}", e),
+        }
+    }
+    Vec::new()
+}
+
+fn append_dead_letter(job: &DeliveryJob) -> Result<(), String> {
+    let mut letters = if Path::new(DEAD_LETTER_FILE).exists() {
+        let contents = fs::read_to_string(DEAD_LETTER_FILE)
+            .map_err(|e| format!("Failed to read dead letter file: {}", e))?;
+        serde_json::from_str::<Vec<DeliveryJob>>(&contents).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    letters.push(job.clone());
+
+    let json = serde_json::to_string_pretty(&letters)
+        .map_err(|e| format!("Failed to serialize dead letters: {}", e))?;
+    fs::write(DEAD_LETTER_FILE, json)
+        .map_err(|e| format!("Failed to write dead letter file: {}", e))
+}
+
+// Background worker spawned from main: polls for due jobs and retries them
+pub async fn run_worker(queue: std::sync::Arc<DeliveryQueue>, state: web::Data<AppState>) {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true) // For testing to accept self-signed certs
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    loop {
+        for job in queue.take_due() {
+            // Re-fetch the endpoint's current config rather than trusting a snapshot
+            // from when the job was enqueued, so retries pick up rotated secrets,
+            // url changes, etc.
+            let endpoints = match state.storage.list_endpoints().await {
+                Ok(endpoints) => endpoints,
+                Err(e) => {
+                    println!("Error listing endpoints for retry of job {}: {}", job.id, e);
+                    queue.reschedule(job);
+                    continue;
+                }
+            };
+            let Some(endpoint) = endpoints.into_iter().find(|e| e.id == job.endpoint_id) else {
+                println!(
+                    "Dropping job {}: endpoint {} no longer exists",
+                    job.id, job.endpoint_id
+                );
+                continue;
+            };
+
+            match forward_and_record(state.storage.as_ref(), &client, &endpoint, &job.payload)
+                .await
+            {
+                Ok(_status) => {
+                    println!("Retry succeeded for job {} to {}", job.id, endpoint.name);
+                }
+                Err(error) => {
+                    println!(
+                        "Retry failed for job {} to {} (attempt {}): {}",
+                        job.id,
+                        endpoint.name,
+                        job.attempts + 1,
+                        error
+                    );
+                    queue.reschedule(job);
+                }
+            }
+        }
+
+        actix_web::rt::time::sleep(std::time::Duration::from_secs(POLL_INTERVAL_SECS)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_delay_grows_exponentially_within_jitter() {
+        for attempts in 0..6 {
+            let raw = BASE_DELAY_SECS * 2u64.pow(attempts);
+            let jitter_range = raw / 5;
+            let delay = backoff_delay_secs(attempts);
+            assert!(
+                delay >= raw.saturating_sub(jitter_range) && delay <= raw + jitter_range,
+                "attempt {}: delay {} outside [{}, {}]",
+                attempts,
+                delay,
+                raw.saturating_sub(jitter_range),
+                raw + jitter_range
+            );
+        }
+    }
+
+    #[test]
+    fn backoff_delay_is_capped_at_max_delay() {
+        let delay = backoff_delay_secs(20);
+        let jitter_range = MAX_DELAY_SECS / 5;
+        assert!(delay <= MAX_DELAY_SECS + jitter_range);
+    }
+}
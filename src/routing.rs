@@ -0,0 +1,116 @@
+// Per-endpoint event filtering and payload transformation, so the relay can
+// act as a router instead of broadcasting every event to every active endpoint.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EventFilterRule {
+    // A `$.a.b.c`-style path evaluated against the webhook payload
+    pub path: String,
+    pub equals: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TransformRule {
+    // Rename top-level fields before forwarding: old name -> new name
+    #[serde(default)]
+    pub field_map: HashMap<String, String>,
+    // Wrap the (possibly remapped) payload in an envelope under this field name
+    #[serde(default)]
+    pub envelope_field: Option<String>,
+}
+
+// Resolve a `$.a.b.c` path against a JSON value, returning the matched value if any
+pub fn resolve_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| current.get(segment))
+}
+
+// All rules must match for the endpoint to receive the event (AND semantics)
+pub fn matches(rules: &[EventFilterRule], payload: &serde_json::Value) -> bool {
+    rules
+        .iter()
+        .all(|rule| resolve_path(payload, &rule.path) == Some(&rule.equals))
+}
+
+pub fn apply_transform(transform: &TransformRule, payload: &serde_json::Value) -> serde_json::Value {
+    let mut value = payload.clone();
+
+    if !transform.field_map.is_empty() {
+        if let serde_json::Value::Object(map) = &mut value {
+            for (old_name, new_name) in &transform.field_map {
+                if let Some(field_value) = map.remove(old_name) {
+                    map.insert(new_name.clone(), field_value);
+                }
+            }
+        }
+    }
+
+    match &transform.envelope_field {
+        Some(field_name) => {
+            let mut envelope = serde_json::Map::new();
+            envelope.insert(field_name.clone(), value);
+            serde_json::Value::Object(envelope)
+        }
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn matches_requires_every_rule_to_match() {
+        let payload = json!({"type": "payment.success", "data": {"amount": 100}});
+        let rules = vec![
+            EventFilterRule {
+                path: "$.type".to_string(),
+                equals: json!("payment.success"),
+            },
+            EventFilterRule {
+                path: "$.data.amount".to_string(),
+                equals: json!(100),
+            },
+        ];
+        assert!(matches(&rules, &payload));
+
+        let rules = vec![EventFilterRule {
+            path: "$.type".to_string(),
+            equals: json!("payment.failed"),
+        }];
+        assert!(!matches(&rules, &payload));
+    }
+
+    #[test]
+    fn matches_with_no_rules_forwards_everything() {
+        let payload = json!({"type": "anything"});
+        assert!(matches(&[], &payload));
+    }
+
+    #[test]
+    fn apply_transform_renames_fields_and_wraps_envelope() {
+        let payload = json!({"old_name": "value", "other": 1});
+        let transform = TransformRule {
+            field_map: [("old_name".to_string(), "new_name".to_string())]
+                .into_iter()
+                .collect(),
+            envelope_field: Some("event".to_string()),
+        };
+
+        let result = apply_transform(&transform, &payload);
+        assert_eq!(
+            result,
+            json!({"event": {"new_name": "value", "other": 1}})
+        );
+    }
+
+    #[test]
+    fn apply_transform_with_no_rules_is_a_passthrough() {
+        let payload = json!({"a": 1});
+        assert_eq!(apply_transform(&TransformRule::default(), &payload), payload);
+    }
+}
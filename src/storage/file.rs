@@ -0,0 +1,278 @@
+// File-backed storage: today's behavior, extracted behind the `Storage` trait.
+use super::{DeliveryRecord, Storage};
+use crate::WebhookEndpoint;
+use async_trait::async_trait;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+const ENDPOINTS_FILE: &str = "endpoints.json";
+const DELIVERIES_FILE: &str = "deliveries.json";
+
+pub struct FileStorage {
+    endpoints: RwLock<Vec<WebhookEndpoint>>,
+    deliveries: RwLock<Vec<DeliveryRecord>>,
+}
+
+impl FileStorage {
+    pub fn load() -> Self {
+        FileStorage {
+            endpoints: RwLock::new(load_endpoints()),
+            deliveries: RwLock::new(load_deliveries()),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for FileStorage {
+    async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>, String> {
+        Ok(self.endpoints.read().unwrap().clone())
+    }
+
+    async fn upsert_endpoint(&self, endpoint: WebhookEndpoint) -> Result<(), String> {
+        let mut endpoints = self.endpoints.write().unwrap();
+        match endpoints.iter_mut().find(|e| e.id == endpoint.id) {
+            Some(existing) => *existing = endpoint,
+            None => endpoints.push(endpoint),
+        }
+        save_endpoints(&endpoints)
+    }
+
+    async fn delete_endpoint(&self, id: &str) -> Result<bool, String> {
+        let mut endpoints = self.endpoints.write().unwrap();
+        let existed = match endpoints.iter().position(|e| e.id == id) {
+            Some(pos) => {
+                endpoints.remove(pos);
+                true
+            }
+            None => false,
+        };
+        if existed {
+            save_endpoints(&endpoints)?;
+        }
+        Ok(existed)
+    }
+
+    async fn record_delivery(&self, record: DeliveryRecord) -> Result<(), String> {
+        let mut deliveries = self.deliveries.write().unwrap();
+        deliveries.push(record);
+        save_deliveries(&deliveries)
+    }
+
+    async fn list_deliveries(&self, endpoint_id: &str) -> Result<Vec<DeliveryRecord>, String> {
+        let mut matching: Vec<DeliveryRecord> = self
+            .deliveries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|d| d.endpoint_id == endpoint_id)
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(matching)
+    }
+}
+
+// Save endpoints to a JSON file
+fn save_endpoints(endpoints: &[WebhookEndpoint]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(endpoints)
+        .map_err(|e| format!("Failed to serialize endpoints: {}", e))?;
+    fs::write(ENDPOINTS_FILE, json).map_err(|e| format!("Failed to write endpoints file: {}", e))
+}
+
+// Load endpoints from a JSON file
+fn load_endpoints() -> Vec<WebhookEndpoint> {
+    if Path::new(ENDPOINTS_FILE).exists() {
+        match fs::read_to_string(ENDPOINTS_FILE) {
+            Ok(contents) => match serde_json::from_str::<Vec<WebhookEndpoint>>(&contents) {
+                Ok(endpoints) => {
+                    println!("Loaded {} endpoints from file", endpoints.len());
+                    return endpoints;
+                }
+                Err(e) => println!("Error parsing endpoints file: {}", e),
+            },
+            Err(e) => println!("Error reading endpoints file: {}", e),
+        }
+    }
+
+    // Return default endpoints with our staging URLs
+    let default_endpoints = vec![
+        WebhookEndpoint {
+            id: "fincra".to_string(),
+            url: "https://staging.webhook.api.mavapay.co/webhook/fincra".to_string(),
+            name: "Fincra Staging".to_string(),
+            is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
+        },
+        WebhookEndpoint {
+            id: "splice".to_string(),
+            url: "https://staging.webhook.api.mavapay.co/webhook/splice".to_string(),
+            name: "Splice Staging".to_string(),
+            is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
+        },
+        WebhookEndpoint {
+            id: "useorange".to_string(),
+            url: "https://staging.webhook.api.mavapay.co/webhook/useorange".to_string(),
+            name: "UseOrange Staging".to_string(),
+            is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
+        },
+        WebhookEndpoint {
+            id: "galoy".to_string(),
+            url: "https://staging.webhook.api.mavapay.co/webhook/galoy".to_string(),
+            name: "Galoy Staging".to_string(),
+            is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
+        },
+    ];
+
+    // Save the default endpoints
+    if let Err(e) = save_endpoints(&default_endpoints) {
+        println!("Error saving default endpoints: {}", e);
+    }
+
+    default_endpoints
+}
+
+fn save_deliveries(deliveries: &[DeliveryRecord]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(deliveries)
+        .map_err(|e| format!("Failed to serialize deliveries: {}", e))?;
+    fs::write(DELIVERIES_FILE, json)
+        .map_err(|e| format!("Failed to write deliveries file: {}", e))
+}
+
+fn load_deliveries() -> Vec<DeliveryRecord> {
+    if Path::new(DELIVERIES_FILE).exists() {
+        match fs::read_to_string(DELIVERIES_FILE) {
+            Ok(contents) => match serde_json::from_str::<Vec<DeliveryRecord>>(&contents) {
+                Ok(deliveries) => return deliveries,
+                Err(e) => println!("Error parsing deliveries file: {}", e),
+            },
+            Err(e) => println!("Error reading deliveries file: {}", e),
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // FileStorage reads/writes fixed filenames in the current directory, so
+    // these tests serialize on a per-test temp directory to avoid racing with
+    // each other (or with a real endpoints.json/deliveries.json in the repo).
+    static CWD_GUARD: Mutex<()> = Mutex::new(());
+
+    struct TempCwd {
+        _guard: std::sync::MutexGuard<'static, ()>,
+        previous: std::path::PathBuf,
+    }
+
+    impl TempCwd {
+        fn enter() -> Self {
+            let guard = CWD_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+            let previous = std::env::current_dir().unwrap();
+            let dir = std::env::temp_dir().join(format!("whd-test-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).unwrap();
+            std::env::set_current_dir(&dir).unwrap();
+            TempCwd {
+                _guard: guard,
+                previous,
+            }
+        }
+    }
+
+    impl Drop for TempCwd {
+        fn drop(&mut self) {
+            std::env::set_current_dir(&self.previous).unwrap();
+        }
+    }
+
+    fn endpoint(id: &str) -> WebhookEndpoint {
+        WebhookEndpoint {
+            id: id.to_string(),
+            url: format!("https://example.com/{}", id),
+            name: id.to_string(),
+            is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
+        }
+    }
+
+    fn delivery(endpoint_id: &str, timestamp: u64) -> DeliveryRecord {
+        DeliveryRecord {
+            endpoint_id: endpoint_id.to_string(),
+            status_code: Some(200),
+            latency_ms: 10,
+            timestamp,
+            error: None,
+            idempotency_key: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn upsert_endpoint_overwrites_by_id_instead_of_duplicating() {
+        let _cwd = TempCwd::enter();
+        let storage = FileStorage {
+            endpoints: RwLock::new(Vec::new()),
+            deliveries: RwLock::new(Vec::new()),
+        };
+        storage.upsert_endpoint(endpoint("a")).await.unwrap();
+        let mut updated = endpoint("a");
+        updated.name = "renamed".to_string();
+        storage.upsert_endpoint(updated).await.unwrap();
+
+        let endpoints = storage.list_endpoints().await.unwrap();
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].name, "renamed");
+
+        // Also verify the on-disk copy round-trips through a fresh load.
+        let reloaded = FileStorage::load();
+        assert_eq!(reloaded.list_endpoints().await.unwrap().len(), 1);
+    }
+
+    #[actix_web::test]
+    async fn delete_endpoint_returns_false_for_unknown_id() {
+        let _cwd = TempCwd::enter();
+        let storage = FileStorage {
+            endpoints: RwLock::new(Vec::new()),
+            deliveries: RwLock::new(Vec::new()),
+        };
+        storage.upsert_endpoint(endpoint("a")).await.unwrap();
+        assert_eq!(storage.delete_endpoint("missing").await.unwrap(), false);
+        assert_eq!(storage.delete_endpoint("a").await.unwrap(), true);
+    }
+
+    #[actix_web::test]
+    async fn list_deliveries_filters_by_endpoint_id_newest_first() {
+        let _cwd = TempCwd::enter();
+        let storage = FileStorage {
+            endpoints: RwLock::new(Vec::new()),
+            deliveries: RwLock::new(Vec::new()),
+        };
+        storage.record_delivery(delivery("a", 1)).await.unwrap();
+        storage.record_delivery(delivery("b", 2)).await.unwrap();
+        storage.record_delivery(delivery("a", 3)).await.unwrap();
+
+        let deliveries = storage.list_deliveries("a").await.unwrap();
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].timestamp, 3);
+        assert_eq!(deliveries[1].timestamp, 1);
+    }
+}
@@ -0,0 +1,126 @@
+// In-memory storage backend, mainly useful for tests and local experimentation
+// where persisting to disk isn't wanted.
+use super::{DeliveryRecord, Storage};
+use crate::WebhookEndpoint;
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+#[derive(Default)]
+pub struct MemoryStorage {
+    endpoints: RwLock<Vec<WebhookEndpoint>>,
+    deliveries: RwLock<Vec<DeliveryRecord>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        MemoryStorage::default()
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>, String> {
+        Ok(self.endpoints.read().unwrap().clone())
+    }
+
+    async fn upsert_endpoint(&self, endpoint: WebhookEndpoint) -> Result<(), String> {
+        let mut endpoints = self.endpoints.write().unwrap();
+        match endpoints.iter_mut().find(|e| e.id == endpoint.id) {
+            Some(existing) => *existing = endpoint,
+            None => endpoints.push(endpoint),
+        }
+        Ok(())
+    }
+
+    async fn delete_endpoint(&self, id: &str) -> Result<bool, String> {
+        let mut endpoints = self.endpoints.write().unwrap();
+        match endpoints.iter().position(|e| e.id == id) {
+            Some(pos) => {
+                endpoints.remove(pos);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn record_delivery(&self, record: DeliveryRecord) -> Result<(), String> {
+        self.deliveries.write().unwrap().push(record);
+        Ok(())
+    }
+
+    async fn list_deliveries(&self, endpoint_id: &str) -> Result<Vec<DeliveryRecord>, String> {
+        let mut matching: Vec<DeliveryRecord> = self
+            .deliveries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|d| d.endpoint_id == endpoint_id)
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(matching)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(id: &str) -> WebhookEndpoint {
+        WebhookEndpoint {
+            id: id.to_string(),
+            url: format!("https://example.com/{}", id),
+            name: id.to_string(),
+            is_active: true,
+            signing_secret: None,
+            event_filter: None,
+            header_allowlist: None,
+            transform: None,
+        }
+    }
+
+    fn delivery(endpoint_id: &str, timestamp: u64) -> DeliveryRecord {
+        DeliveryRecord {
+            endpoint_id: endpoint_id.to_string(),
+            status_code: Some(200),
+            latency_ms: 10,
+            timestamp,
+            error: None,
+            idempotency_key: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn upsert_endpoint_overwrites_by_id_instead_of_duplicating() {
+        let storage = MemoryStorage::new();
+        storage.upsert_endpoint(endpoint("a")).await.unwrap();
+        let mut updated = endpoint("a");
+        updated.name = "renamed".to_string();
+        storage.upsert_endpoint(updated).await.unwrap();
+
+        let endpoints = storage.list_endpoints().await.unwrap();
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].name, "renamed");
+    }
+
+    #[actix_web::test]
+    async fn delete_endpoint_returns_false_for_unknown_id() {
+        let storage = MemoryStorage::new();
+        storage.upsert_endpoint(endpoint("a")).await.unwrap();
+        assert_eq!(storage.delete_endpoint("missing").await.unwrap(), false);
+        assert_eq!(storage.delete_endpoint("a").await.unwrap(), true);
+    }
+
+    #[actix_web::test]
+    async fn list_deliveries_filters_by_endpoint_id_newest_first() {
+        let storage = MemoryStorage::new();
+        storage.record_delivery(delivery("a", 1)).await.unwrap();
+        storage.record_delivery(delivery("b", 2)).await.unwrap();
+        storage.record_delivery(delivery("a", 3)).await.unwrap();
+
+        let deliveries = storage.list_deliveries("a").await.unwrap();
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].timestamp, 3);
+        assert_eq!(deliveries[1].timestamp, 1);
+    }
+}
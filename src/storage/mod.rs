@@ -0,0 +1,63 @@
+// Storage abstraction: where endpoints and delivery history live. `AppState`
+// holds a `Box<dyn Storage>` so the backend can be swapped via `STORAGE_BACKEND`
+// without touching any handler code.
+mod file;
+mod memory;
+mod postgres;
+
+pub use file::FileStorage;
+pub use memory::MemoryStorage;
+pub use postgres::PostgresStorage;
+
+use crate::WebhookEndpoint;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeliveryRecord {
+    pub endpoint_id: String,
+    pub status_code: Option<u16>,
+    pub latency_ms: u128,
+    pub timestamp: u64,
+    pub error: Option<String>,
+    // The inbound idempotency key that produced this delivery, if one was computed,
+    // so operators can trace which original event produced which forwards.
+    pub idempotency_key: Option<String>,
+}
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>, String>;
+    async fn upsert_endpoint(&self, endpoint: WebhookEndpoint) -> Result<(), String>;
+    // Returns whether an endpoint with this id existed and was removed
+    async fn delete_endpoint(&self, id: &str) -> Result<bool, String>;
+    async fn record_delivery(&self, record: DeliveryRecord) -> Result<(), String>;
+    // Returns delivery records for `endpoint_id`, newest first, regardless of backend
+    async fn list_deliveries(&self, endpoint_id: &str) -> Result<Vec<DeliveryRecord>, String>;
+}
+
+// Select a backend based on the `STORAGE_BACKEND` env var (`file`, `memory`,
+// `postgres`), defaulting to `file` to preserve today's behavior.
+pub async fn from_env() -> Box<dyn Storage> {
+    match std::env::var("STORAGE_BACKEND").unwrap_or_else(|_| "file".to_string()).as_str() {
+        "memory" => Box::new(MemoryStorage::new()),
+        "postgres" => {
+            let database_url = std::env::var("DATABASE_URL")
+                .expect("DATABASE_URL must be set when STORAGE_BACKEND=postgres");
+            Box::new(
+                PostgresStorage::connect(&database_url)
+                    .await
+                    .expect("Failed to connect to Postgres storage backend"),
+            )
+        }
+        other => {
+            if other != "file" {
+                println!(
+                    "Unknown STORAGE_BACKEND '{}', falling back to file storage",
+                    other
+                );
+            }
+            Box::new(FileStorage::load())
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// Postgres-backed storage, for deployments that want endpoints and delivery
+// history to survive beyond a single host's disk.
+use super::{DeliveryRecord, Storage};
+use crate::{EventFilterRule, TransformRule, WebhookEndpoint};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+pub struct PostgresStorage {
+    pool: PgPool,
+}
+
+impl PostgresStorage {
+    pub async fn connect(database_url: &str) -> Result<Self, String> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .map_err(|e| format!("Failed to connect to Postgres: {}", e))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS endpoints (
+                id TEXT PRIMARY KEY,
+                url TEXT NOT NULL,
+                name TEXT NOT NULL,
+                is_active BOOLEAN NOT NULL,
+                signing_secret TEXT,
+                event_filter TEXT,
+                header_allowlist TEXT,
+                transform TEXT
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to create endpoints table: {}", e))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS deliveries (
+                endpoint_id TEXT NOT NULL,
+                status_code INTEGER,
+                latency_ms BIGINT NOT NULL,
+                timestamp BIGINT NOT NULL,
+                error TEXT,
+                idempotency_key TEXT
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to create deliveries table: {}", e))?;
+
+        Ok(PostgresStorage { pool })
+    }
+}
+
+// event_filter/header_allowlist/transform are stored as JSON text columns since
+// they're nested structures without an obvious flat-column representation.
+fn encode_json<T: serde::Serialize>(value: &Option<T>) -> Option<String> {
+    value
+        .as_ref()
+        .map(|v| serde_json::to_string(v).expect("routing rules are always serializable"))
+}
+
+fn decode_json<T: DeserializeOwned>(value: Option<String>) -> Result<Option<T>, String> {
+    value
+        .map(|raw| serde_json::from_str(&raw).map_err(|e| format!("Failed to decode column: {}", e)))
+        .transpose()
+}
+
+#[async_trait]
+impl Storage for PostgresStorage {
+    async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>, String> {
+        sqlx::query(
+            "SELECT id, url, name, is_active, signing_secret, event_filter, header_allowlist, transform
+             FROM endpoints",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to list endpoints: {}", e))?
+        .into_iter()
+        .map(|row| {
+            Ok(WebhookEndpoint {
+                id: row.try_get("id").map_err(|e| e.to_string())?,
+                url: row.try_get("url").map_err(|e| e.to_string())?,
+                name: row.try_get("name").map_err(|e| e.to_string())?,
+                is_active: row.try_get("is_active").map_err(|e| e.to_string())?,
+                signing_secret: row.try_get("signing_secret").map_err(|e| e.to_string())?,
+                event_filter: decode_json::<Vec<EventFilterRule>>(
+                    row.try_get("event_filter").map_err(|e| e.to_string())?,
+                )?,
+                header_allowlist: decode_json::<Vec<String>>(
+                    row.try_get("header_allowlist").map_err(|e| e.to_string())?,
+                )?,
+                transform: decode_json::<TransformRule>(
+                    row.try_get("transform").map_err(|e| e.to_string())?,
+                )?,
+            })
+        })
+        .collect()
+    }
+
+    async fn upsert_endpoint(&self, endpoint: WebhookEndpoint) -> Result<(), String> {
+        sqlx::query(
+            "INSERT INTO endpoints
+                (id, url, name, is_active, signing_secret, event_filter, header_allowlist, transform)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (id) DO UPDATE SET
+                url = EXCLUDED.url,
+                name = EXCLUDED.name,
+                is_active = EXCLUDED.is_active,
+                signing_secret = EXCLUDED.signing_secret,
+                event_filter = EXCLUDED.event_filter,
+                header_allowlist = EXCLUDED.header_allowlist,
+                transform = EXCLUDED.transform",
+        )
+        .bind(&endpoint.id)
+        .bind(&endpoint.url)
+        .bind(&endpoint.name)
+        .bind(endpoint.is_active)
+        .bind(&endpoint.signing_secret)
+        .bind(encode_json(&endpoint.event_filter))
+        .bind(encode_json(&endpoint.header_allowlist))
+        .bind(encode_json(&endpoint.transform))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to upsert endpoint: {}", e))?;
+        Ok(())
+    }
+
+    async fn delete_endpoint(&self, id: &str) -> Result<bool, String> {
+        let result = sqlx::query("DELETE FROM endpoints WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete endpoint: {}", e))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_delivery(&self, record: DeliveryRecord) -> Result<(), String> {
+        sqlx::query(
+            "INSERT INTO deliveries
+                (endpoint_id, status_code, latency_ms, timestamp, error, idempotency_key)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&record.endpoint_id)
+        .bind(record.status_code.map(|s| s as i32))
+        .bind(record.latency_ms as i64)
+        .bind(record.timestamp as i64)
+        .bind(&record.error)
+        .bind(&record.idempotency_key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to record delivery: {}", e))?;
+        Ok(())
+    }
+
+    async fn list_deliveries(&self, endpoint_id: &str) -> Result<Vec<DeliveryRecord>, String> {
+        sqlx::query(
+            "SELECT endpoint_id, status_code, latency_ms, timestamp, error, idempotency_key
+             FROM deliveries WHERE endpoint_id = $1 ORDER BY timestamp DESC",
+        )
+        .bind(endpoint_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to list deliveries: {}", e))?
+        .into_iter()
+        .map(|row| {
+            let status_code: Option<i32> = row.try_get("status_code").map_err(|e| e.to_string())?;
+            let latency_ms: i64 = row.try_get("latency_ms").map_err(|e| e.to_string())?;
+            let timestamp: i64 = row.try_get("timestamp").map_err(|e| e.to_string())?;
+            Ok(DeliveryRecord {
+                endpoint_id: row.try_get("endpoint_id").map_err(|e| e.to_string())?,
+                status_code: status_code.map(|s| s as u16),
+                latency_ms: latency_ms as u128,
+                timestamp: timestamp as u64,
+                error: row.try_get("error").map_err(|e| e.to_string())?,
+                idempotency_key: row.try_get("idempotency_key").map_err(|e| e.to_string())?,
+            })
+        })
+        .collect()
+    }
+}